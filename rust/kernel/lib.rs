@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! The `kernel` crate, which contains the Rust bindings and glue code used
+//! by Rust kernel modules.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod spi;
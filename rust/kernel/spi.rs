@@ -5,7 +5,77 @@ use crate::c_types;
 use crate::error::{Error, Result};
 use crate::CStr;
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::future::Future;
 use core::pin::Pin;
+use core::sync::atomic::{AtomicI32, AtomicU8, Ordering};
+use core::task::{Context, Poll, Waker};
+
+/// Clock polarity, i.e. the idle state of the clock line (`SPI_CPOL`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// Clock idles low.
+    IdleLow,
+    /// Clock idles high.
+    IdleHigh,
+}
+
+/// Clock phase, i.e. which clock edge data is sampled on (`SPI_CPHA`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Data is sampled on the leading clock edge.
+    SampleLeading,
+    /// Data is sampled on the trailing clock edge.
+    SampleTrailing,
+}
+
+/// The four standard SPI clock modes, built from a [`Polarity`]/[`Phase`]
+/// pair plus the remaining `SPI_*` mode bits (`SPI_CS_HIGH`, `SPI_LSB_FIRST`,
+/// `SPI_3WIRE`, ...) passed through unchanged.
+#[derive(Clone, Copy)]
+pub struct Mode {
+    pub polarity: Polarity,
+    pub phase: Phase,
+    other_bits: u32,
+}
+
+impl Mode {
+    pub fn new(polarity: Polarity, phase: Phase) -> Self {
+        Mode {
+            polarity,
+            phase,
+            other_bits: 0,
+        }
+    }
+
+    fn from_bits(bits: u32) -> Self {
+        Mode {
+            polarity: if bits & bindings::SPI_CPOL != 0 {
+                Polarity::IdleHigh
+            } else {
+                Polarity::IdleLow
+            },
+            phase: if bits & bindings::SPI_CPHA != 0 {
+                Phase::SampleTrailing
+            } else {
+                Phase::SampleLeading
+            },
+            other_bits: bits & !(bindings::SPI_CPOL | bindings::SPI_CPHA),
+        }
+    }
+
+    fn to_bits(self) -> u32 {
+        let mut bits = self.other_bits;
+        if self.polarity == Polarity::IdleHigh {
+            bits |= bindings::SPI_CPOL;
+        }
+        if self.phase == Phase::SampleTrailing {
+            bits |= bindings::SPI_CPHA;
+        }
+        bits
+    }
+}
 
 #[derive(Clone, Copy)]
 pub struct SpiDevice(*mut bindings::spi_device);
@@ -18,6 +88,122 @@ impl SpiDevice {
     pub fn to_ptr(&mut self) -> *mut bindings::spi_device {
         self.0
     }
+
+    /// Returns the device's current clock mode.
+    pub fn mode(&self) -> Mode {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        Mode::from_bits(unsafe { (*self.0).mode } as u32)
+    }
+
+    /// Sets the clock mode that will be applied on the next [`setup`].
+    ///
+    /// [`setup`]: SpiDevice::setup
+    pub fn set_mode(&mut self, mode: Mode) {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        unsafe { (*self.0).mode = mode.to_bits() as _ };
+    }
+
+    /// Returns the device's current word size, in bits.
+    pub fn bits_per_word(&self) -> u8 {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        unsafe { (*self.0).bits_per_word }
+    }
+
+    /// Sets the word size that will be applied on the next [`setup`].
+    ///
+    /// [`setup`]: SpiDevice::setup
+    pub fn set_bits_per_word(&mut self, bits_per_word: u8) {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        unsafe { (*self.0).bits_per_word = bits_per_word };
+    }
+
+    /// Returns the device's current bus clock speed, in Hz.
+    pub fn max_speed_hz(&self) -> u32 {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        unsafe { (*self.0).max_speed_hz }
+    }
+
+    /// Sets the bus clock speed that will be applied on the next [`setup`].
+    ///
+    /// [`setup`]: SpiDevice::setup
+    pub fn set_max_speed_hz(&mut self, max_speed_hz: u32) {
+        // SAFETY: `self.0` is a valid `spi_device` for the lifetime of `self`.
+        unsafe { (*self.0).max_speed_hz = max_speed_hz };
+    }
+
+    /// Validates the mode/bits-per-word/speed currently set on this device
+    /// and applies them to the controller, via `spi_setup`.
+    ///
+    /// Call this from `probe` after adjusting any of the fields above and
+    /// before the first transfer.
+    pub fn setup(&mut self) -> Result {
+        let res = unsafe { bindings::spi_setup(self.to_ptr()) };
+        match res {
+            0 => Ok(()),
+            err => Err(Error::from_kernel_errno(err)),
+        }
+    }
+}
+
+/// A Rust SPI driver, analogous to a C `spi_driver`.
+///
+/// `probe` returns the per-device state to keep around for the lifetime of
+/// the device; `remove` and `shutdown` get that same state back, mirroring
+/// how the kernel's `device_driver` callbacks drive a device through
+/// probe/remove/shutdown.
+pub trait Driver {
+    /// Per-device state allocated by `probe` and released after `remove`.
+    type Data;
+
+    fn probe(dev: &mut SpiDevice) -> Result<Self::Data>;
+
+    fn remove(_dev: &mut SpiDevice, _data: &mut Self::Data) -> Result {
+        Ok(())
+    }
+
+    fn shutdown(_dev: &mut SpiDevice, _data: &mut Self::Data) {}
+}
+
+/// Generates the `extern "C"` probe/remove/shutdown trampolines for a
+/// [`Driver`] implementation, boxing its `Data` and threading it through
+/// `spi_set_drvdata`/`spi_get_drvdata`.
+struct Adapter<T: Driver>(core::marker::PhantomData<T>);
+
+impl<T: Driver> Adapter<T> {
+    unsafe extern "C" fn probe_callback(dev: *mut bindings::spi_device) -> c_types::c_int {
+        let mut spi_dev = SpiDevice::from_ptr(dev);
+        match T::probe(&mut spi_dev) {
+            Ok(data) => match Box::try_new(data) {
+                Ok(data) => {
+                    bindings::spi_set_drvdata(dev, Box::into_raw(data) as *mut c_types::c_void);
+                    0
+                }
+                Err(_) => Error::ENOMEM.to_kernel_errno(),
+            },
+            Err(e) => e.to_kernel_errno(),
+        }
+    }
+
+    unsafe extern "C" fn remove_callback(dev: *mut bindings::spi_device) -> c_types::c_int {
+        let mut spi_dev = SpiDevice::from_ptr(dev);
+        let data = bindings::spi_get_drvdata(dev) as *mut T::Data;
+
+        let res = T::remove(&mut spi_dev, &mut *data);
+        // SAFETY: `data` was boxed in `probe_callback` and this is the only
+        // place it is ever freed.
+        drop(Box::from_raw(data));
+
+        match res {
+            Ok(()) => 0,
+            Err(e) => e.to_kernel_errno(),
+        }
+    }
+
+    unsafe extern "C" fn shutdown_callback(dev: *mut bindings::spi_device) {
+        let mut spi_dev = SpiDevice::from_ptr(dev);
+        let data = bindings::spi_get_drvdata(dev) as *mut T::Data;
+        T::shutdown(&mut spi_dev, &mut *data);
+    }
 }
 
 pub struct DriverRegistration {
@@ -70,6 +256,23 @@ impl DriverRegistration {
         Ok(registration)
     }
 
+    /// Builds and registers a driver from a [`Driver`] implementation,
+    /// wiring up [`Adapter`]'s generated trampolines as the probe/remove/
+    /// shutdown callbacks so `T::Data` is threaded through the device's
+    /// lifetime automatically.
+    pub fn new_pinned_for_driver<T: Driver>(
+        this_module: &'static crate::ThisModule,
+        name: CStr<'static>,
+    ) -> Result<Pin<Box<Self>>> {
+        Self::new_pinned(
+            this_module,
+            name,
+            Some(Adapter::<T>::probe_callback),
+            Some(Adapter::<T>::remove_callback),
+            Some(Adapter::<T>::shutdown_callback),
+        )
+    }
+
     // FIXME: Add documentation
     pub fn register(self: Pin<&mut Self>) -> Result {
         let this = unsafe { self.get_unchecked_mut() };
@@ -174,4 +377,446 @@ impl Spi {
     pub fn read(dev: &mut SpiDevice, rx_buf: &mut [u8], n_rx: usize) -> Result {
         Spi::write_then_read(dev, &[0u8; 0], 0, rx_buf, n_rx)
     }
+
+    /// Submits `msg` through `spi_async` and returns a future that resolves
+    /// once the controller has run its completion callback.
+    ///
+    /// Unlike `write_then_read`, this does not block the calling thread: the
+    /// transfer is driven by the controller's own workqueue/IRQ path and the
+    /// returned future only completes when `spi_message.complete` fires.
+    pub fn transfer_async(dev: &mut SpiDevice, msg: SpiMessage) -> Result<SpiTransfer> {
+        let mut inner = msg.inner;
+
+        // SAFETY: `inner` is uniquely owned at this point (it has not been
+        // submitted yet), so taking a mutable reference through the pin is
+        // sound; nothing else can be observing it concurrently.
+        let inner_mut = unsafe { inner.as_mut().get_unchecked_mut() };
+        inner_mut.message.complete = Some(spi_message_complete_trampoline);
+        inner_mut.message.context = inner_mut as *mut SpiMessageInner as *mut c_types::c_void;
+
+        let res = unsafe { bindings::spi_async(dev.to_ptr(), &mut inner_mut.message) };
+        if res != 0 {
+            return Err(Error::from_kernel_errno(res));
+        }
+
+        Ok(SpiTransfer { inner: Some(inner) })
+    }
+}
+
+const SUBMITTED: u8 = 0;
+const COMPLETED: u8 = 1;
+const DETACHED: u8 = 2;
+
+/// Per-message completion state shared between the polling future, the
+/// `spi_message.complete` trampoline, and `SpiTransfer::drop`.
+///
+/// `state` serves two purposes: it is the completion flag `poll` checks,
+/// and it arbitrates which side is responsible for freeing the boxed
+/// `SpiMessageInner` if `SpiTransfer` is dropped before completion. Both
+/// the trampoline and `drop` attempt the same `SUBMITTED -> {COMPLETED,
+/// DETACHED}` transition via `compare_exchange`, so exactly one of them
+/// wins and only that side acts; there is no window where both (or
+/// neither) believe they own the free.
+struct Completion {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: the `waker` slot is only written from `register()`, and only read
+// back from `complete()` after winning the `state` transition out of
+// `SUBMITTED`, so the two never race on it.
+unsafe impl Sync for Completion {}
+
+impl Completion {
+    fn new() -> Self {
+        Completion {
+            state: AtomicU8::new(SUBMITTED),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.state.load(Ordering::Acquire) == COMPLETED
+    }
+
+    /// Registers `waker` to be woken on completion, then re-checks for
+    /// completion before returning whether it already happened.
+    ///
+    /// The caller must register before trusting a "not done yet" result:
+    /// if completion lands between an earlier `is_done()` check and this
+    /// call, it finds no waker to wake, and a waker stored after that point
+    /// is never woken; re-checking after storing it here observes such a
+    /// completion instead of leaving the future pending.
+    fn register(&self, waker: &Waker) -> bool {
+        // SAFETY: see the synchronization argument on the struct doc comment.
+        unsafe { *self.waker.get() = Some(waker.clone()) };
+        self.is_done()
+    }
+
+    /// Called from the completion trampoline. Returns `true` if the caller
+    /// must free the boxed `SpiMessageInner` itself, because `SpiTransfer`
+    /// was dropped first and detached it; `false` if the normal
+    /// `SpiTransfer`/`poll` path still owns it.
+    fn complete(&self) -> bool {
+        match self
+            .state
+            .compare_exchange(SUBMITTED, COMPLETED, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                // SAFETY: see the synchronization argument on the struct doc
+                // comment; we just won the transition out of `SUBMITTED`.
+                if let Some(waker) = unsafe { (*self.waker.get()).take() } {
+                    waker.wake();
+                }
+                false
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Called from `SpiTransfer::drop` before completion has been observed.
+    /// Returns `true` if the drop must hand the allocation off to the
+    /// trampoline to free later, because it won the race against
+    /// completion; `false` if completion actually ran first, in which case
+    /// the ordinary `Box` drop is safe to run here.
+    fn detach(&self) -> bool {
+        self.state
+            .compare_exchange(SUBMITTED, DETACHED, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+}
+
+/// One TX/RX segment of an [`SpiMessage`], owning the buffers it transfers.
+pub struct SpiSegment {
+    pub tx: Option<Box<[u8]>>,
+    pub rx: Option<Box<[u8]>>,
+}
+
+struct SpiMessageInner {
+    message: bindings::spi_message,
+    // Kept alive for as long as `message` links into it; never resized
+    // after construction, since `spi_message_add_tail` stores pointers into
+    // this storage.
+    transfers: Vec<bindings::spi_transfer>,
+    segments: Vec<SpiSegment>,
+    completion: Completion,
+    status: AtomicI32,
+}
+
+/// An owned `spi_message` built from one or more [`SpiSegment`]s, ready to
+/// be submitted with [`Spi::transfer_async`].
+///
+/// The message and its transfer buffers are boxed and pinned so their
+/// address is stable for as long as the controller may reference them.
+pub struct SpiMessage {
+    inner: Pin<Box<SpiMessageInner>>,
+}
+
+impl SpiMessage {
+    pub fn new(mut segments: Vec<SpiSegment>) -> Result<Self> {
+        let mut transfers = Vec::new();
+        transfers
+            .try_reserve_exact(segments.len())
+            .map_err(|_| Error::ENOMEM)?;
+        for segment in segments.iter_mut() {
+            let mut transfer = bindings::spi_transfer::default();
+            if let Some(tx) = &segment.tx {
+                transfer.tx_buf = tx.as_ptr() as *const c_types::c_void;
+                transfer.len = tx.len() as u32;
+            }
+            if let Some(rx) = &mut segment.rx {
+                transfer.rx_buf = rx.as_mut_ptr() as *mut c_types::c_void;
+                transfer.len = rx.len() as u32;
+            }
+            transfers.push(transfer);
+        }
+
+        let mut inner = Box::try_new(SpiMessageInner {
+            message: bindings::spi_message::default(),
+            transfers,
+            segments,
+            completion: Completion::new(),
+            status: AtomicI32::new(0),
+        })?;
+
+        // SAFETY: `inner.message` and `inner.transfers` are freshly
+        // allocated and not yet visible to the controller.
+        unsafe {
+            bindings::spi_message_init(&mut inner.message);
+            for transfer in inner.transfers.iter_mut() {
+                bindings::spi_message_add_tail(transfer, &mut inner.message);
+            }
+        }
+
+        Ok(SpiMessage {
+            inner: Pin::from(inner),
+        })
+    }
+}
+
+// SAFETY: `spi_message.complete` is invoked by the controller with the
+// `context` pointer stashed on submission, which is always the address of
+// the matching `SpiMessageInner`.
+unsafe extern "C" fn spi_message_complete_trampoline(context: *mut c_types::c_void) {
+    let inner = &*(context as *const SpiMessageInner);
+    inner.status.store(inner.message.status, Ordering::Release);
+
+    if inner.completion.complete() {
+        // `SpiTransfer` was dropped before this callback ran and detached
+        // the allocation to us; we are now its sole owner and must free it.
+        drop(Box::from_raw(context as *mut SpiMessageInner));
+    }
+}
+
+/// A future representing an in-flight transfer submitted with
+/// [`Spi::transfer_async`].
+///
+/// Resolves to the segments that were transferred, with their `rx` buffers
+/// filled in by the controller.
+pub struct SpiTransfer {
+    inner: Option<Pin<Box<SpiMessageInner>>>,
+}
+
+impl Future for SpiTransfer {
+    type Output = Result<Vec<SpiSegment>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner_ref = self.inner.as_ref().expect("SpiTransfer polled after completion");
+
+        if !inner_ref.completion.is_done() {
+            // Register before trusting the result: `register` re-checks
+            // completion after storing the waker, so a completion that
+            // races in right here is still observed instead of being
+            // missed.
+            if !inner_ref.completion.register(cx.waker()) {
+                return Poll::Pending;
+            }
+        }
+
+        // SAFETY: completion has observably run, so the controller no
+        // longer touches the message and we may reclaim the allocation.
+        let inner = unsafe { Pin::into_inner_unchecked(self.inner.take().unwrap()) };
+        let status = inner.status.load(Ordering::Acquire);
+        let SpiMessageInner {
+            segments, ..
+        } = *inner;
+
+        match status {
+            0 => Poll::Ready(Ok(segments)),
+            err => Poll::Ready(Err(Error::from_kernel_errno(err))),
+        }
+    }
+}
+
+impl Drop for SpiTransfer {
+    fn drop(&mut self) {
+        let inner = match self.inner.take() {
+            Some(inner) => inner,
+            // Already completed and reclaimed by `poll`.
+            None => return,
+        };
+
+        if inner.completion.detach() {
+            // We won the race against completion: the controller may still
+            // be using the message, so hand the allocation off to the
+            // trampoline instead of freeing it here. It will free it once
+            // `spi_message.complete` actually fires.
+            //
+            // SAFETY: we are giving up ownership to the trampoline, which
+            // reconstructs the `Box` from this same raw pointer.
+            let raw = unsafe { Pin::into_inner_unchecked(inner) };
+            Box::leak(raw);
+        }
+        // Otherwise completion won the race and already ran; `inner` frees
+        // normally here.
+    }
+}
+
+/// A builder for a `spi_message` made of several segments, run synchronously
+/// with `spi_sync` once [`submit`] is called.
+///
+/// Unlike [`Spi::write_then_read`], which is hardcoded to a single TX
+/// segment followed by a single RX segment, this assembles an arbitrary
+/// chain of segments, e.g. a command segment followed by a data segment, or
+/// a full-duplex segment with equal-length TX/RX buffers. The lifetime
+/// parameter ties every borrowed buffer to the builder, so the borrow
+/// checker rejects a `submit()` that would outlive any of them.
+///
+/// [`submit`]: Transfer::submit
+///
+/// ```ignore
+/// Transfer::new(dev)
+///     .write(&cmd)
+///     .delay_us(10)
+///     .cs_change()
+///     .read(&mut data)
+///     .submit()?;
+/// ```
+pub struct Transfer<'a> {
+    dev: &'a mut SpiDevice,
+    transfers: Vec<bindings::spi_transfer>,
+    // Recorded by a builder method that caught an invalid argument, and
+    // returned by `submit()` instead of panicking immediately: a plain
+    // caller mistake (e.g. mismatched tx/rx lengths) should fail the
+    // transfer, not take the kernel down.
+    error: Option<Error>,
+}
+
+impl<'a> Transfer<'a> {
+    pub fn new(dev: &'a mut SpiDevice) -> Self {
+        Transfer {
+            dev,
+            transfers: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Appends a TX-only segment.
+    pub fn write(mut self, tx: &'a [u8]) -> Self {
+        let mut transfer = bindings::spi_transfer::default();
+        transfer.tx_buf = tx.as_ptr() as *const c_types::c_void;
+        transfer.len = tx.len() as u32;
+        self.transfers.push(transfer);
+        self
+    }
+
+    /// Appends an RX-only segment.
+    pub fn read(mut self, rx: &'a mut [u8]) -> Self {
+        let mut transfer = bindings::spi_transfer::default();
+        transfer.rx_buf = rx.as_mut_ptr() as *mut c_types::c_void;
+        transfer.len = rx.len() as u32;
+        self.transfers.push(transfer);
+        self
+    }
+
+    /// Appends a full-duplex segment: `tx` is clocked out while `rx` is
+    /// clocked in over the same transfer. `tx` and `rx` must be the same
+    /// length, or `submit()` will return `Err(EINVAL)`.
+    pub fn duplex(mut self, tx: &'a [u8], rx: &'a mut [u8]) -> Self {
+        if tx.len() != rx.len() {
+            self.error.get_or_insert(Error::EINVAL);
+            return self;
+        }
+
+        let mut transfer = bindings::spi_transfer::default();
+        transfer.tx_buf = tx.as_ptr() as *const c_types::c_void;
+        transfer.rx_buf = rx.as_mut_ptr() as *mut c_types::c_void;
+        transfer.len = tx.len() as u32;
+        self.transfers.push(transfer);
+        self
+    }
+
+    /// Delays `delay_us` microseconds after the most recently added segment,
+    /// before the next one (or chip-select deassertion) begins.
+    pub fn delay_us(mut self, delay_us: u16) -> Self {
+        if let Some(transfer) = self.transfers.last_mut() {
+            transfer.delay_usecs = delay_us;
+        }
+        self
+    }
+
+    /// Toggles chip-select between the most recently added segment and the
+    /// next one, instead of leaving it asserted across the whole message.
+    pub fn cs_change(mut self) -> Self {
+        if let Some(transfer) = self.transfers.last_mut() {
+            transfer.cs_change = 1;
+        }
+        self
+    }
+
+    /// Overrides the bus speed for the most recently added segment.
+    pub fn speed_hz(mut self, speed_hz: u32) -> Self {
+        if let Some(transfer) = self.transfers.last_mut() {
+            transfer.speed_hz = speed_hz;
+        }
+        self
+    }
+
+    /// Overrides the word size for the most recently added segment.
+    pub fn bits_per_word(mut self, bits_per_word: u8) -> Self {
+        if let Some(transfer) = self.transfers.last_mut() {
+            transfer.bits_per_word = bits_per_word;
+        }
+        self
+    }
+
+    /// Runs the assembled segments as a single message via `spi_sync`,
+    /// blocking until the controller completes it.
+    pub fn submit(mut self) -> Result {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let mut message = bindings::spi_message::default();
+
+        // SAFETY: `message` is local and not yet visible to the controller;
+        // `self.transfers` outlives the call below and is not resized after
+        // this point.
+        unsafe {
+            bindings::spi_message_init(&mut message);
+            for transfer in self.transfers.iter_mut() {
+                bindings::spi_message_add_tail(transfer, &mut message);
+            }
+        }
+
+        let res = unsafe { bindings::spi_sync(self.dev.to_ptr(), &mut message) };
+        match res {
+            0 => Ok(()),
+            err => Err(Error::from_kernel_errno(err)),
+        }
+    }
+}
+
+/// Bridges [`SpiDevice`] to the `embedded-hal` SPI bus traits, so the large
+/// body of existing `embedded-hal` peripheral drivers can run unmodified as
+/// kernel modules on top of the kernel's SPI core.
+#[cfg(feature = "embedded-hal")]
+pub mod embedded_hal_compat {
+    use super::{Spi, SpiDevice};
+    use crate::error::Error;
+    use alloc::vec::Vec;
+    use embedded_hal::blocking::spi::{Transfer, Write};
+
+    /// Newtype over [`SpiDevice`] implementing the `embedded-hal` blocking
+    /// SPI bus traits.
+    pub struct EmbeddedHalSpi(pub SpiDevice);
+
+    /// The error type for [`EmbeddedHalSpi`]'s trait impls: a kernel errno
+    /// from the underlying transfer.
+    #[derive(Debug)]
+    pub struct SpiError(pub Error);
+
+    impl From<Error> for SpiError {
+        fn from(err: Error) -> Self {
+            SpiError(err)
+        }
+    }
+
+    impl Write<u8> for EmbeddedHalSpi {
+        type Error = SpiError;
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            let len = words.len();
+            Spi::write(&mut self.0, words, len).map_err(SpiError)
+        }
+    }
+
+    impl Transfer<u8> for EmbeddedHalSpi {
+        type Error = SpiError;
+
+        fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+            // `write_then_read` takes separate tx/rx buffers, but
+            // `embedded-hal`'s `Transfer` clocks `words` out and reads the
+            // reply back into the same buffer, so stage the outgoing bytes
+            // in a scratch copy first. Reserve fallibly rather than via
+            // `to_vec()`, which aborts the kernel on allocation failure.
+            let mut tx: Vec<u8> = Vec::new();
+            tx.try_reserve_exact(words.len())
+                .map_err(|_| SpiError(Error::ENOMEM))?;
+            tx.extend_from_slice(words);
+            let len = words.len();
+            Spi::write_then_read(&mut self.0, &tx, len, words, len).map_err(SpiError)?;
+            Ok(words)
+        }
+    }
 }
@@ -25,9 +25,15 @@ impl spi::Driver for SpiDummy {
         (SpiDeviceId::new(c_str!("SpiDummy"), 42usize), None),
     ]);
 
-    fn probe(spi: &mut SpiDevice) -> Result<i32> {
+    fn probe(spi: &mut SpiDevice) -> Result<Self::Data> {
         pr_info!("[SPI-RS] probed\n");
 
         Ok(0)
     }
+
+    fn remove(_spi: &mut SpiDevice, _data: &mut Self::Data) -> Result {
+        pr_info!("[SPI-RS] removed\n");
+
+        Ok(())
+    }
 }